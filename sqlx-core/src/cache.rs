@@ -0,0 +1,203 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+
+/// A bounded, least-recently-used cache from SQL text to a prepared statement handle.
+///
+/// This is generic over the statement handle type `S` so that each driver can plug in
+/// whatever it uses to refer to a server-side prepared statement (e.g. an allocated
+/// statement ID for Postgres/MySQL, or a raw `sqlite3_stmt` pointer for SQLite).
+///
+/// The cache is keyed on the *borrowed* `&str` of the query text (see [`QueryString`]'s
+/// `Borrow<str>` impl) so a lookup never needs to allocate, even though the stored key
+/// is an owned `Box<str>`.
+///
+/// Eviction follows strict LRU: every successful [`get_mut`][Self::get_mut] moves the
+/// entry to the back of the map, and [`insert`][Self::insert] pops from the front once
+/// `len() > capacity`. The caller is responsible for reacting to an eviction (typically
+/// by issuing `DEALLOCATE` for the evicted statement).
+///
+/// [`QueryString`]: crate::query_string::QueryString
+pub struct StatementCache<S> {
+    inner: IndexMap<Box<str>, S>,
+    capacity: usize,
+}
+
+impl<S> StatementCache<S> {
+    /// Create a new cache with the given capacity.
+    ///
+    /// A capacity of `0` disables caching entirely: [`insert`][Self::insert] will
+    /// immediately return the value it was just given instead of storing it.
+    pub fn new(capacity: usize) -> Self {
+        StatementCache {
+            inner: IndexMap::new(),
+            capacity,
+        }
+    }
+
+    /// Returns `true` if this cache will store entries at all.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// The maximum number of entries this cache will hold before evicting.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of entries currently stored.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Look up `key`, marking it as most-recently-used if found.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut S>
+    where
+        Box<str>: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.inner.get_index_of(key)?;
+
+        // Move the entry to the end of the map so the front stays in LRU order.
+        self.inner.move_index(index, self.inner.len() - 1);
+
+        self.inner.last_mut().map(|(_, value)| value)
+    }
+
+    /// Insert a new entry, marking it as most-recently-used.
+    ///
+    /// If the cache is disabled (`capacity == 0`), returns [`InsertOutcome::NotCached`]
+    /// with `value` handed straight back so the caller can react (typically by
+    /// deallocating the statement it just prepared, since nothing will reuse it).
+    /// Otherwise returns [`InsertOutcome::Evicted`] if this pushed the cache over
+    /// capacity, naming the least-recently-used entry the caller must now deallocate,
+    /// or [`InsertOutcome::Cached`] if it fit without evicting anything.
+    pub fn insert(&mut self, key: &str, value: S) -> InsertOutcome<S> {
+        if self.capacity == 0 {
+            return InsertOutcome::NotCached(value);
+        }
+
+        if let Some(index) = self.inner.get_index_of(key) {
+            // `IndexMap::insert` on an already-present key overwrites the value in
+            // place *without* moving it, which would silently break LRU order (the
+            // entry would stay wherever it was, not become most-recently-used). Remove
+            // it first so the re-insert below appends it at the back instead.
+            self.inner.shift_remove_index(index);
+        }
+
+        self.inner.insert(key.into(), value);
+
+        if self.inner.len() > self.capacity {
+            // `shift_remove_index(0)` evicts the least-recently-used entry,
+            // which sits at the front after our `move_index`/re-insert calls above.
+            let (evicted_key, evicted_value) = self
+                .inner
+                .shift_remove_index(0)
+                .expect("len() > capacity >= 1, so the map is non-empty");
+
+            return InsertOutcome::Evicted(evicted_key, evicted_value);
+        }
+
+        InsertOutcome::Cached
+    }
+
+    /// Remove every entry from the cache, returning them in LRU order (oldest first).
+    pub fn clear(&mut self) -> impl Iterator<Item = (Box<str>, S)> + '_ {
+        self.inner.drain(..)
+    }
+}
+
+/// The outcome of [`StatementCache::insert`].
+#[derive(Debug)]
+pub enum InsertOutcome<S> {
+    /// The entry was stored without evicting anything.
+    Cached,
+    /// The cache is disabled (`capacity == 0`); `S` was handed straight back and
+    /// nothing was stored.
+    NotCached(S),
+    /// Storing the entry pushed the cache over capacity; this is the least-recently-used
+    /// entry that was evicted to make room.
+    Evicted(Box<str>, S),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_caches_and_looks_up_entries() {
+        let mut cache = StatementCache::new(2);
+
+        assert!(matches!(cache.insert("SELECT 1", 1), InsertOutcome::Cached));
+        assert!(matches!(cache.insert("SELECT 2", 2), InsertOutcome::Cached));
+
+        assert_eq!(cache.get_mut("SELECT 1"), Some(&mut 1));
+        assert_eq!(cache.get_mut("SELECT 2"), Some(&mut 2));
+        assert_eq!(cache.get_mut("SELECT 3"), None);
+    }
+
+    #[test]
+    fn it_evicts_the_least_recently_used_entry() {
+        let mut cache = StatementCache::new(2);
+
+        cache.insert("SELECT 1", 1);
+        cache.insert("SELECT 2", 2);
+
+        // Touching "SELECT 1" makes "SELECT 2" the least-recently-used entry.
+        assert_eq!(cache.get_mut("SELECT 1"), Some(&mut 1));
+
+        match cache.insert("SELECT 3", 3) {
+            InsertOutcome::Evicted(key, value) => {
+                assert_eq!(&*key, "SELECT 2");
+                assert_eq!(value, 2);
+            }
+            other => panic!("expected an eviction, got {other:?}"),
+        }
+
+        assert_eq!(cache.get_mut("SELECT 1"), Some(&mut 1));
+        assert_eq!(cache.get_mut("SELECT 2"), None);
+        assert_eq!(cache.get_mut("SELECT 3"), Some(&mut 3));
+    }
+
+    #[test]
+    fn re_inserting_an_existing_key_moves_it_to_most_recently_used() {
+        let mut cache = StatementCache::new(2);
+
+        cache.insert("SELECT 1", 1);
+        cache.insert("SELECT 2", 2);
+
+        // Re-inserting "SELECT 1" should make "SELECT 2" the least-recently-used entry,
+        // even though "SELECT 1" was never touched via `get_mut` afterward.
+        assert!(matches!(
+            cache.insert("SELECT 1", 10),
+            InsertOutcome::Cached
+        ));
+
+        match cache.insert("SELECT 3", 3) {
+            InsertOutcome::Evicted(key, value) => {
+                assert_eq!(&*key, "SELECT 2");
+                assert_eq!(value, 2);
+            }
+            other => panic!("expected an eviction, got {other:?}"),
+        }
+
+        assert_eq!(cache.get_mut("SELECT 1"), Some(&mut 10));
+    }
+
+    #[test]
+    fn a_disabled_cache_hands_the_value_straight_back() {
+        let mut cache: StatementCache<i32> = StatementCache::new(0);
+
+        match cache.insert("SELECT 1", 1) {
+            InsertOutcome::NotCached(value) => assert_eq!(value, 1),
+            other => panic!("expected `NotCached`, got {other:?}"),
+        }
+
+        assert_eq!(cache.len(), 0);
+        assert_eq!(cache.get_mut("SELECT 1"), None);
+    }
+}
@@ -0,0 +1,74 @@
+//! The native [`Transport`](super::Transport) implementation: plain TCP/Unix sockets.
+//! No TLS wrapper is provided here; [`NativeSocket`] carries the raw socket bytes only.
+//!
+//! Everything in this module requires real OS sockets, so it's entirely gated behind each driver's `*-native`
+//! feature and is simply absent from a `wasm32-unknown-unknown` build -- see
+//! [`super::wasm`] for what takes its place there.
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::io::{AsyncRead, AsyncWrite};
+use tokio::io::{AsyncRead as TokioAsyncRead, AsyncWrite as TokioAsyncWrite};
+use tokio::net::TcpStream;
+
+#[cfg(unix)]
+use tokio::net::UnixStream;
+
+/// Either a TCP or (on Unix) a Unix domain socket, connected to a database server.
+///
+/// This is the concrete, native-only counterpart of [`Transport`](super::Transport) --
+/// the protocol layer never names this type directly, only the trait.
+pub enum NativeSocket {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+macro_rules! with_inner {
+    ($self:ident, $inner:ident => $method:ident($($arg:expr),* $(,)?)) => {
+        match $self.get_mut() {
+            NativeSocket::Tcp($inner) => Pin::new($inner).$method($($arg),*),
+            #[cfg(unix)]
+            NativeSocket::Unix($inner) => Pin::new($inner).$method($($arg),*),
+        }
+    };
+}
+
+// `tokio::net` streams implement tokio's `AsyncRead`/`AsyncWrite`, not `futures_util`'s;
+// these impls bridge the two so the rest of sqlx-core only has to depend on one set of
+// traits regardless of runtime.
+impl AsyncRead for NativeSocket {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut read_buf = tokio::io::ReadBuf::new(buf);
+
+        match with_inner!(self, s => poll_read(cx, &mut read_buf)) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(read_buf.filled().len())),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl AsyncWrite for NativeSocket {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        with_inner!(self, s => poll_write(cx, buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        with_inner!(self, s => poll_flush(cx))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        with_inner!(self, s => poll_shutdown(cx))
+    }
+}
@@ -0,0 +1,36 @@
+use futures_util::io::{AsyncRead, AsyncWrite};
+
+/// A byte-stream connection to a database server, abstracted away from how that
+/// connection was actually established.
+///
+/// This is the seam that lets the protocol layer of each driver -- `QueryString`/
+/// `QuerySafeStr`, `StatementRef` serialization, and the `Decode`/`Serialize` types like
+/// `BackendKeyData` -- compile for `wasm32-unknown-unknown`, following the native-vs-wasm
+/// connector split used by Prisma's quaint.
+///
+/// Those protocol types only ever read and write plain `&[u8]` buffers; none of them
+/// touch a socket, the filesystem, or TLS directly. Everything that *does* need real
+/// I/O goes through a `Transport` impl instead:
+///
+/// * On native targets (gated behind each driver's `*-native` Cargo feature), `Transport`
+///   is implemented for a plain TCP/Unix socket (no TLS wrapper is provided) -- see
+///   [`native`].
+/// * On `wasm32-unknown-unknown`, there is no socket API to speak of. Instead a host
+///   environment (e.g. a browser or edge runtime) supplies its own transport -- for
+///   example a JS-backed adapter bridging to `fetch`/`WebSocket`/a driver provided by the
+///   embedder -- and that adapter implements `Transport` and feeds the bytes it receives
+///   into the same `Decode` paths the native transport uses. See [`wasm`].
+///
+/// Drivers' protocol code depends only on this trait, never on a concrete stream type, so
+/// swapping the transport at compile time doesn't touch a single line of protocol code.
+/// Only native-only constructors (e.g. Postgres's `connect_tcp`) name a concrete socket
+/// type.
+pub trait Transport: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T> Transport for T where T: AsyncRead + AsyncWrite + Unpin + Send {}
+
+#[cfg(feature = "_rt-native")]
+pub mod native;
+
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
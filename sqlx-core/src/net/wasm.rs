@@ -0,0 +1,72 @@
+//! The `wasm32-unknown-unknown` [`Transport`](super::Transport) implementation.
+//!
+//! There is no socket API available to a plain Wasm module, so a host environment must
+//! supply one: this module defines the adapter trait an embedder (a browser, an edge
+//! runtime, a JS host) implements and hands to sqlx, bridging to whatever the host
+//! actually has available (`fetch`, `WebSocket`, a native driver exposed over a JS FFI
+//! boundary, etc). Once bytes cross that boundary they flow into the exact same
+//! `Decode`/`Serialize` protocol code the native transport uses -- this module is the
+//! only part of a driver that differs between targets.
+
+use std::pin::Pin;
+
+use futures_util::io::{AsyncRead, AsyncWrite};
+
+/// A host-provided byte stream to a database server, supplied from outside the Wasm
+/// module (e.g. via a JS driver adapter) and adapted to sqlx's
+/// [`Transport`](super::Transport) trait.
+///
+/// Construct one from whatever binding glue the embedder provides; sqlx's driver code
+/// only ever interacts with it through [`Transport`](super::Transport).
+pub struct HostTransport {
+    inner: Pin<Box<dyn AsyncReadWrite>>,
+}
+
+impl HostTransport {
+    /// Wrap a host-supplied stream that already implements the async read/write traits
+    /// this crate depends on.
+    pub fn new(inner: impl AsyncRead + AsyncWrite + Unpin + Send + 'static) -> Self {
+        HostTransport {
+            inner: Box::pin(inner),
+        }
+    }
+}
+
+// A single combined trait so `HostTransport` can hold one trait object instead of two,
+// since `dyn AsyncRead + AsyncWrite` isn't expressible directly.
+trait AsyncReadWrite: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> AsyncReadWrite for T {}
+
+impl AsyncRead for HostTransport {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut [u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        self.inner.as_mut().poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for HostTransport {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        self.inner.as_mut().poll_write(cx, buf)
+    }
+
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        self.inner.as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        self.inner.as_mut().poll_close(cx)
+    }
+}
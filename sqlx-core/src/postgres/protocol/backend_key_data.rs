@@ -3,6 +3,9 @@ use crate::io::Buf;
 use byteorder::NetworkEndian;
 use std::io;
 
+// Pure buffer decoding, no socket/TLS/filesystem I/O -- this (and the rest of the
+// protocol layer) compiles for `wasm32-unknown-unknown` unchanged. See
+// `crate::net::transport` for where the native/wasm split actually happens.
 #[derive(Debug)]
 pub struct BackendKeyData {
     /// The process ID of this backend.
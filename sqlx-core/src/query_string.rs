@@ -39,7 +39,10 @@ impl QuerySafeStr<'static> for &'static str {
     #[inline]
 
     fn into_query_string(self) -> QueryString<'static> {
-        QueryString(Repr::Static(self))
+        QueryString {
+            repr: Repr::Static(self),
+            cacheable: true,
+        }
     }
 }
 
@@ -137,21 +140,30 @@ where
 {
     #[inline]
     fn into_query_string(self) -> QueryString<'a> {
-        QueryString(Repr::Slice(self.0.as_ref()))
+        QueryString {
+            repr: Repr::Slice(self.0.as_ref()),
+            cacheable: true,
+        }
     }
 }
 
 impl QuerySafeStr<'static> for AssertQuerySafe<String> {
     #[inline]
     fn into_query_string(self) -> QueryString<'static> {
-        QueryString(Repr::Owned(self.0))
+        QueryString {
+            repr: Repr::Owned(self.0),
+            cacheable: true,
+        }
     }
 }
 
 impl QuerySafeStr<'static> for AssertQuerySafe<Box<str>> {
     #[inline]
     fn into_query_string(self) -> QueryString<'static> {
-        QueryString(Repr::Boxed(self.0))
+        QueryString {
+            repr: Repr::Boxed(self.0),
+            cacheable: true,
+        }
     }
 }
 
@@ -159,7 +171,47 @@ impl QuerySafeStr<'static> for AssertQuerySafe<Box<str>> {
 impl QuerySafeStr<'static> for AssertQuerySafe<Arc<str>> {
     #[inline]
     fn into_query_string(self) -> QueryString<'static> {
-        QueryString(Repr::Arced(self.0))
+        QueryString {
+            repr: Repr::Arced(self.0),
+            cacheable: true,
+        }
+    }
+}
+
+/// Assert that a query string should never be cached as a server-side prepared statement.
+///
+/// Wrap any [`QuerySafeStr`] value (typically an [`AssertQuerySafe`]) with this to force
+/// [`QueryString::is_cacheable`] to `false`.
+///
+/// Use this for statements that expand to an unbounded number of distinct SQL texts, e.g.
+/// a dynamically-sized `IN (...)` list that was inlined into the query instead of bound
+/// as a single array parameter. Without this, each distinct text would permanently occupy
+/// a slot in the connection's statement cache, evicting statements that are actually
+/// reused and ultimately causing the cache to thrash.
+///
+/// ```rust
+/// use sqlx_core::query_string::{AssertQuerySafe, AssertQueryUncacheable};
+///
+/// # fn example(ids: &[i64]) -> sqlx_core::query_string::QueryString<'static> {
+/// use sqlx_core::query_string::QuerySafeStr;
+///
+/// let placeholders: String = ids.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+/// let query = format!("SELECT * FROM foo WHERE id IN ({placeholders})");
+///
+/// AssertQueryUncacheable(AssertQuerySafe(query)).into_query_string()
+/// # }
+/// ```
+pub struct AssertQueryUncacheable<T>(pub T);
+
+impl<'a, T> QuerySafeStr<'a> for AssertQueryUncacheable<T>
+where
+    T: QuerySafeStr<'a>,
+{
+    #[inline]
+    fn into_query_string(self) -> QueryString<'a> {
+        let mut query = self.0.into_query_string();
+        query.cacheable = false;
+        query
     }
 }
 
@@ -173,7 +225,12 @@ impl QuerySafeStr<'static> for AssertQuerySafe<Arc<str>> {
 ///
 /// This type is not designed to be manually constructable.
 #[derive(Clone, Debug)]
-pub struct QueryString<'a>(Repr<'a>);
+pub struct QueryString<'a> {
+    repr: Repr<'a>,
+    // Whether this query string may be inserted into a connection's prepared statement
+    // cache. Defaults to `true`; cleared via [`AssertQueryUncacheable`].
+    cacheable: bool,
+}
 
 #[derive(Clone, Debug)]
 enum Repr<'a> {
@@ -212,19 +269,22 @@ impl QueryString<'_> {
     /// In all other cases, this is a no-op.
     #[inline]
     pub fn into_static(self) -> QueryString<'static> {
-        QueryString(match self.0 {
-            Repr::Slice(s) => Repr::Boxed(s.into()),
-            Repr::Static(s) => Repr::Static(s),
-            Repr::Owned(s) => Repr::Owned(s),
-            Repr::Boxed(s) => Repr::Boxed(s),
-            Repr::Arced(s) => Repr::Arced(s),
-        })
+        QueryString {
+            repr: match self.repr {
+                Repr::Slice(s) => Repr::Boxed(s.into()),
+                Repr::Static(s) => Repr::Static(s),
+                Repr::Owned(s) => Repr::Owned(s),
+                Repr::Boxed(s) => Repr::Boxed(s),
+                Repr::Arced(s) => Repr::Arced(s),
+            },
+            cacheable: self.cacheable,
+        }
     }
 
     /// Borrow the inner query string.
     #[inline]
     pub fn as_str(&self) -> &str {
-        match &self.0 {
+        match &self.repr {
             Repr::Slice(s) => s,
             Repr::Static(s) => s,
             Repr::Owned(s) => s,
@@ -232,6 +292,15 @@ impl QueryString<'_> {
             Repr::Arced(s) => s,
         }
     }
+
+    /// Returns `false` if this query string was constructed via [`AssertQueryUncacheable`]
+    /// and so must never be inserted into a connection's prepared statement cache.
+    ///
+    /// Defaults to `true` for all other constructors.
+    #[inline]
+    pub fn is_cacheable(&self) -> bool {
+        self.cacheable
+    }
 }
 
 impl AsRef<str> for QueryString<'_> {
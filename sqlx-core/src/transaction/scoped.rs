@@ -0,0 +1,148 @@
+use std::future::Future;
+
+use crate::acquire::Acquire;
+use crate::error::Error;
+use crate::transaction::Transaction;
+
+/// How many times [`transaction`] should retry the closure after a retryable error.
+///
+/// Defaults to not retrying at all (`max_retries: 0`), since retrying is only safe for
+/// errors the caller's closure is actually idempotent with respect to; opt in
+/// explicitly with [`RetryPolicy::retries`].
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    should_retry: fn(&Error) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            should_retry: is_retryable,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Retry up to `max_retries` times, using the default classification of which
+    /// errors are worth retrying (serialization failures and other transaction
+    /// rollback conditions -- see [`is_retryable`]).
+    ///
+    /// This classification is backend-agnostic by design. A driver-specific condition
+    /// like SQLite's `SQLITE_BUSY` isn't covered by it; use
+    /// [`retry_if`][Self::retry_if] with a predicate built on that driver's own error
+    /// type if you need to retry on those too.
+    pub fn retries(max_retries: u32) -> Self {
+        RetryPolicy {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// Use a custom predicate to decide whether a given error should trigger a retry,
+    /// instead of the default classification.
+    pub fn retry_if(max_retries: u32, should_retry: fn(&Error) -> bool) -> Self {
+        RetryPolicy {
+            max_retries,
+            should_retry,
+        }
+    }
+}
+
+/// The default retryable-error classification used by [`RetryPolicy::retries`]:
+/// serialization/deadlock failures reported as a transaction rollback, which are the
+/// conflict errors a caller can usually just retry as-is.
+///
+/// Deliberately backend-agnostic: `sqlx-core` has no business knowing about a specific
+/// driver's result codes (e.g. SQLite's `SQLITE_BUSY`). A caller that wants to retry on
+/// those too should reach for [`RetryPolicy::retry_if`] instead.
+fn is_retryable(e: &Error) -> bool {
+    let Some(db_err) = e.as_database_error() else {
+        return false;
+    };
+
+    db_err.is_transaction_rollback()
+}
+
+/// Run `f` inside a single transaction: begin, run the closure, commit on `Ok`, and
+/// roll back on `Err` -- the "whole block is one transaction" pattern, so callers don't
+/// have to manually juggle a [`Transaction`] guard and remember to roll back on every
+/// early return.
+///
+/// The closure receives a `&mut Transaction<DB>`; every query built against it runs
+/// inside the same transaction. If the closure returns `Err`, or panics, the
+/// transaction is rolled back -- the panic case falls out of `Transaction`'s `Drop`
+/// impl, which already issues `ROLLBACK` for any transaction that wasn't explicitly
+/// committed, so this combinator doesn't need to (and can't, across an unwind) do
+/// anything special to guarantee it.
+///
+/// Pass a non-default [`RetryPolicy`] to re-run the closure a bounded number of times
+/// when it fails with a retryable conflict (e.g. a serialization failure reported as a
+/// transaction rollback), rather than making every caller reimplement that loop by
+/// hand.
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::SqlitePool) -> sqlx::Result<()> {
+/// use sqlx_core::transaction::{transaction, RetryPolicy};
+///
+/// let inserted_id: i64 = transaction(&pool, RetryPolicy::retries(3), |tx| {
+///     Box::pin(async move {
+///         let id = sqlx::query_scalar("INSERT INTO users (name) VALUES (?) RETURNING id")
+///             .bind("alice")
+///             .fetch_one(&mut **tx)
+///             .await?;
+///
+///         Ok(id)
+///     })
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn transaction<'a, A, F, T>(
+    acquire: A,
+    retry_policy: RetryPolicy,
+    mut f: F,
+) -> Result<T, Error>
+where
+    A: Acquire<'a> + Clone,
+    F: for<'t> FnMut(
+        &'t mut Transaction<'a, A::Database>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<T, Error>> + Send + 't>>,
+{
+    let mut attempt = 0;
+
+    loop {
+        let mut tx = acquire.clone().begin().await?;
+
+        let result = match f(&mut tx).await {
+            // The closure succeeded, but the error that actually matters for
+            // `should_retry` -- a serialization failure -- often only surfaces here,
+            // at `COMMIT` (e.g. Postgres `SERIALIZABLE`), not mid-transaction. So a
+            // commit failure has to go through the exact same retry check as a
+            // closure error, not propagate straight out via `?`.
+            Ok(value) => tx.commit().await.map(|()| value),
+            Err(e) => {
+                // `tx` is dropped here regardless of whether `rollback()` itself
+                // succeeds; either way the connection won't be reused with an open
+                // transaction still pending.
+                let _ = tx.rollback().await;
+                Err(e)
+            }
+        };
+
+        match result {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                if attempt < retry_policy.max_retries && (retry_policy.should_retry)(&e) {
+                    attempt += 1;
+                    continue;
+                }
+
+                return Err(e);
+            }
+        }
+    }
+}
+
@@ -0,0 +1,111 @@
+mod stmt_cache;
+
+use sqlx_core::io::Serialize;
+use sqlx_core::net::transport::Transport;
+use sqlx_core::query_string::QueryString;
+use sqlx_core::Result;
+
+use crate::protocol::frontend::statement::StatementRef;
+use crate::stream::PgStream;
+
+use stmt_cache::{CacheAction, PreparedStatementCache};
+
+/// A single open connection to a Postgres server.
+///
+/// Generic over `T` so the same driver code compiles against a native TCP/Unix socket
+/// or a host-supplied [`Transport`] on `wasm32-unknown-unknown` -- see
+/// [`PgStream`](crate::stream::PgStream). This only wires the prepared-statement cache
+/// through the connection -- the rest of `PgConnection` (the handshake, authentication,
+/// decoding backend messages) lives alongside it in the driver and isn't duplicated
+/// here.
+pub struct PgConnection<T: Transport> {
+    stream: PgStream<T>,
+    stmt_cache: PreparedStatementCache,
+}
+
+impl<T: Transport> PgConnection<T> {
+    /// Resolve `query` to a [`StatementRef`] ready to bind parameters against and
+    /// execute, `PREPARE`ing it on the server first if needed.
+    ///
+    /// This is the one place [`PreparedStatementCache`] is actually consulted: a cache
+    /// hit reuses the already-`PREPARE`d statement id outright; a miss either allocates
+    /// a new named id (for a cacheable query) or falls back to
+    /// [`StatementRef::Unnamed`] (for one that isn't, per [`QueryString::is_cacheable`]),
+    /// sends the `Parse` message, and on a cacheable miss records the result via
+    /// [`insert`][PreparedStatementCache::insert] -- issuing `Close` for whatever
+    /// statement that insertion evicted to make room.
+    pub(crate) async fn prepare(&mut self, query: &QueryString<'_>) -> Result<StatementRef> {
+        match self
+            .stmt_cache
+            .get_or_prepare(query.as_str(), query.is_cacheable())
+        {
+            CacheAction::Hit(stmt) => Ok(stmt),
+            CacheAction::Miss { should_cache } => {
+                let stmt = if should_cache {
+                    StatementRef::Named(self.stmt_cache.allocate_id())
+                } else {
+                    StatementRef::Unnamed
+                };
+
+                self.send_parse(query.as_str(), &stmt).await?;
+
+                if let StatementRef::Named(id) = stmt {
+                    if let Some(evicted) =
+                        self.stmt_cache.insert(query.as_str(), StatementRef::Named(id))
+                    {
+                        self.send_close(&evicted).await?;
+                    }
+                }
+
+                Ok(stmt)
+            }
+        }
+    }
+
+    /// Send a `Parse` message naming `stmt` (or leaving it unnamed) for `sql`.
+    ///
+    /// This only shows where the cache's decision actually reaches the wire; the rest
+    /// of the message (parameter type hints) and reading back `ParseComplete` lives
+    /// alongside this in the rest of the driver.
+    async fn send_parse(&mut self, sql: &str, stmt: &StatementRef) -> Result<()> {
+        let mut buf = Vec::new();
+        stmt.serialize_with(&mut buf, ())?;
+        buf.extend_from_slice(sql.as_bytes());
+        buf.push(0);
+
+        self.stream.write_message(&buf).await
+    }
+
+    /// Send a `Close` message for a statement this connection is done with, after it
+    /// was evicted from [`PreparedStatementCache`] to make room for a new entry.
+    async fn send_close(&mut self, stmt: &StatementRef) -> Result<()> {
+        let mut buf = Vec::new();
+        buf.push(b'S');
+        stmt.serialize_with(&mut buf, ())?;
+
+        self.stream.write_message(&buf).await
+    }
+}
+
+#[cfg(feature = "_rt-native")]
+impl PgConnection<sqlx_core::net::native::NativeSocket> {
+    /// Connect to a Postgres server over a plain TCP socket at `host:port`.
+    ///
+    /// Only available on native targets; no constructor for a host-supplied
+    /// [`Transport`](sqlx_core::net::transport::Transport) is defined here. The rest of
+    /// the connection (the handshake, authentication) lives alongside it in the driver
+    /// and isn't duplicated here.
+    pub(crate) async fn connect_tcp(host: &str, port: u16) -> Result<Self> {
+        use sqlx_core::error::Error;
+        use sqlx_core::net::native::NativeSocket;
+
+        let tcp = tokio::net::TcpStream::connect((host, port))
+            .await
+            .map_err(Error::Io)?;
+
+        Ok(PgConnection {
+            stream: PgStream::new(NativeSocket::Tcp(tcp)),
+            stmt_cache: PreparedStatementCache::new(100),
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use sqlx_core::cache::{InsertOutcome, StatementCache};
+
+use crate::protocol::frontend::statement::StatementRef;
+
+/// Allocates and caches named prepared statements for a single Postgres connection.
+///
+/// This follows the first two buckets of Diesel's three-bucket scheme for deciding how a
+/// given SQL text gets executed:
+///
+/// * **Unsafe to cache** -- [`QueryString::is_cacheable`] is `false` (see
+///   [`AssertQueryUncacheable`]). These always run as [`StatementRef::Unnamed`] and are
+///   never looked up or inserted.
+/// * **Cached by SQL** -- the common case handled here: the raw query text is the cache
+///   key, and a hit reuses the same [`StatementRef::Named`] id that was `PREPARE`d for it
+///   previously.
+/// * **Cached by type** -- not handled: there is no separate by-type bucket, so compiled
+///   queries (`query!`/`query_as!`) are cached by their SQL text like any other query.
+///
+/// [`QueryString::is_cacheable`]: sqlx_core::query_string::QueryString::is_cacheable
+/// [`AssertQueryUncacheable`]: sqlx_core::query_string::AssertQueryUncacheable
+pub(crate) struct PreparedStatementCache {
+    cache: StatementCache<StatementRef>,
+    next_id: u32,
+}
+
+/// The outcome of [`PreparedStatementCache::get_or_prepare`].
+pub(crate) enum CacheAction {
+    /// The SQL text was already cached; reuse this existing server-side statement.
+    Hit(StatementRef),
+    /// The SQL text was not cached (either a cache miss, or caching is disallowed for
+    /// this query). The caller must prepare the statement and, if `should_cache` is
+    /// `true`, report it back via [`PreparedStatementCache::insert`].
+    Miss { should_cache: bool },
+}
+
+impl PreparedStatementCache {
+    /// Create a new cache that will hold at most `capacity` named prepared statements.
+    pub(crate) fn new(capacity: usize) -> Self {
+        PreparedStatementCache {
+            cache: StatementCache::new(capacity),
+            next_id: 0,
+        }
+    }
+
+    /// Look up `sql`, returning a reusable [`StatementRef`] on a hit.
+    ///
+    /// `cacheable` should come from [`QueryString::is_cacheable`]; when `false` this
+    /// always returns a [`CacheAction::Miss`] with `should_cache: false` so the caller
+    /// knows to use [`StatementRef::Unnamed`] rather than allocating a new id.
+    ///
+    /// [`QueryString::is_cacheable`]: sqlx_core::query_string::QueryString::is_cacheable
+    pub(crate) fn get_or_prepare(&mut self, sql: &str, cacheable: bool) -> CacheAction {
+        if !cacheable || !self.cache.is_enabled() {
+            return CacheAction::Miss {
+                should_cache: false,
+            };
+        }
+
+        if let Some(stmt) = self.cache.get_mut(sql) {
+            return CacheAction::Hit(StatementRef::Named(match stmt {
+                StatementRef::Named(id) => *id,
+                StatementRef::Unnamed => unreachable!("cache never stores `Unnamed`"),
+            }));
+        }
+
+        CacheAction::Miss {
+            should_cache: true,
+        }
+    }
+
+    /// Allocate the next named statement id, to be `PREPARE`d by the caller.
+    pub(crate) fn allocate_id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Record that `sql` was just `PREPARE`d as `stmt`.
+    ///
+    /// Returns the evicted statement, if inserting this entry pushed the cache over
+    /// capacity; the caller is responsible for issuing `DEALLOCATE` for it.
+    pub(crate) fn insert(&mut self, sql: &str, stmt: StatementRef) -> Option<StatementRef> {
+        match self.cache.insert(sql, stmt) {
+            InsertOutcome::Evicted(_, evicted) => Some(evicted),
+            // `get_or_prepare` already checked `cacheable`/`is_enabled` before deciding
+            // to prepare at all, so `NotCached` shouldn't happen in practice -- but
+            // handle it the same as `Cached` (nothing to deallocate) rather than panic.
+            InsertOutcome::Cached | InsertOutcome::NotCached(_) => None,
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use sqlx_core::io::Serialize;
 use sqlx_core::Result;
 
+// Pure buffer serialization, no socket/TLS/filesystem I/O -- this (and the rest of the
+// protocol layer) compiles for `wasm32-unknown-unknown` unchanged. See
+// `sqlx_core::net::transport` for where the native/wasm split actually happens.
 #[derive(Debug)]
 pub(crate) enum StatementRef {
     Unnamed,
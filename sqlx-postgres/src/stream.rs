@@ -0,0 +1,27 @@
+use futures_util::io::AsyncWriteExt;
+
+use sqlx_core::error::Error;
+use sqlx_core::net::transport::Transport;
+use sqlx_core::Result;
+
+/// The wire connection to a Postgres server, generic over how that connection was
+/// actually established (a native TCP/Unix socket, or a host-supplied transport on
+/// `wasm32-unknown-unknown`) -- see [`Transport`].
+///
+/// [`PgConnection`](crate::PgConnection) only ever talks to this, never to a concrete
+/// socket type, so it compiles against either target by swapping the `T` it's
+/// instantiated with.
+pub(crate) struct PgStream<T: Transport> {
+    transport: T,
+}
+
+impl<T: Transport> PgStream<T> {
+    pub(crate) fn new(transport: T) -> Self {
+        PgStream { transport }
+    }
+
+    /// Write an already-serialized frontend message to the wire.
+    pub(crate) async fn write_message(&mut self, buf: &[u8]) -> Result<()> {
+        self.transport.write_all(buf).await.map_err(Error::Io)
+    }
+}
@@ -0,0 +1,55 @@
+use std::ptr;
+
+use libsqlite3_sys::{
+    sqlite3_open_v2, SQLITE_OPEN_CREATE, SQLITE_OPEN_FULLMUTEX, SQLITE_OPEN_READWRITE,
+};
+
+use crate::extension::path_to_cstring;
+use crate::options::preload_extensions;
+use crate::{SqliteConnectOptions, SqliteConnection, SqliteError};
+
+/// Open a new connection per `options`, then preload every extension registered via
+/// [`SqliteConnectOptions::extension`]/[`extension_with_entrypoint`][ewe] on it before
+/// handing it back.
+///
+/// This is the one place those two steps actually happen for a real connection --
+/// without it, `options.extensions` would sit on `SqliteConnectOptions` unused by
+/// anything.
+///
+/// [ewe]: SqliteConnectOptions::extension_with_entrypoint
+pub(crate) async fn establish(options: &SqliteConnectOptions) -> Result<SqliteConnection, SqliteError> {
+    let filename = path_to_cstring(&options.filename);
+
+    // `sqlite3_open_v2` blocks on file-system I/O, so it runs on a blocking thread
+    // rather than whatever task is driving this future; there's no connection (and
+    // thus no worker thread) to dispatch it through yet.
+    let (rc, handle) = tokio::task::spawn_blocking(move || {
+        let mut handle = ptr::null_mut();
+
+        // SAFETY: `filename` is a valid, nul-terminated path for the duration of this
+        // call; `handle` is only read after checking the return code below.
+        let rc = unsafe {
+            sqlite3_open_v2(
+                filename.as_ptr(),
+                &mut handle,
+                SQLITE_OPEN_READWRITE | SQLITE_OPEN_CREATE | SQLITE_OPEN_FULLMUTEX,
+                ptr::null(),
+            )
+        };
+
+        (rc, handle)
+    })
+    .await
+    .expect("blocking task panicked");
+
+    SqliteError::from_code(rc)?;
+
+    let handle = std::ptr::NonNull::new(handle)
+        .expect("sqlite3_open_v2 returned SQLITE_OK with a null handle");
+
+    let mut conn = SqliteConnection::from_raw_parts(handle);
+
+    preload_extensions(&mut conn, &options.extensions).await?;
+
+    Ok(conn)
+}
@@ -0,0 +1,110 @@
+pub(crate) mod establish;
+mod worker;
+
+use std::os::raw::c_int;
+use std::ptr::NonNull;
+
+use libsqlite3_sys::{sqlite3, sqlite3_close};
+
+pub(crate) use worker::Worker;
+
+use crate::hooks::Hooks;
+use crate::{SqliteConnectOptions, SqliteError};
+
+/// A single open connection to a SQLite database file (or an in-memory database).
+///
+/// The blocking libsqlite3 calls made through this connection -- executing a
+/// statement, stepping an [online backup](crate::Backup), reading or writing a
+/// [`Blob`](crate::Blob), recording a [`Session`](crate::Session)'s changeset -- are
+/// dispatched onto one dedicated worker thread via
+/// [`run_blocking`][Self::run_blocking], since SQLite connections aren't safe to use
+/// concurrently from multiple threads without the "serialized" threading mode, which
+/// this driver doesn't require callers to enable. Opening a blob, loading an extension,
+/// registering hooks and closing handles are made inline on the calling thread instead.
+pub struct SqliteConnection {
+    handle: NonNull<sqlite3>,
+    worker: Worker,
+    pub(crate) hooks: Hooks,
+}
+
+// SAFETY: `handle` is only used through `&mut self` -- blocking calls on `worker`'s
+// dedicated thread (see `run_blocking`), the rest inline -- so it's never used from two
+// threads at once; moving a `SqliteConnection` to another thread just moves the sending
+// half of that thread's job channel, which is itself `Send`.
+unsafe impl Send for SqliteConnection {}
+
+impl SqliteConnection {
+    pub(crate) fn as_raw_handle(&mut self) -> *mut sqlite3 {
+        self.handle.as_ptr()
+    }
+
+    /// The most recent SQLite result code recorded on this connection
+    /// (`sqlite3_errcode`), for APIs like `sqlite3_backup_init` that report failure
+    /// out-of-band instead of through their own return value.
+    ///
+    /// # Safety
+    /// `self` must be a valid, open connection.
+    pub(crate) unsafe fn last_error_code(&self) -> c_int {
+        // SAFETY: delegated to the caller; `self.handle` is valid for as long as
+        // `self` is alive.
+        unsafe { libsqlite3_sys::sqlite3_errcode(self.handle.as_ptr()) }
+    }
+
+    /// Run `f` on this connection's dedicated worker thread and await the result.
+    ///
+    /// Use this for any libsqlite3 call that can block on disk I/O or another
+    /// connection's lock -- executing a statement, an online backup step, a BLOB
+    /// read/write, serializing a session's changeset -- so it never blocks the async
+    /// runtime's own threads. Calls that can't block (e.g. reading a flag off the
+    /// connection) are fine to make directly against
+    /// [`as_raw_handle`][Self::as_raw_handle].
+    pub(crate) async fn run_blocking<F, T>(&mut self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+        T: Send,
+    {
+        self.worker.run(f).await
+    }
+
+    /// Build a connection directly from an already-open handle and worker thread.
+    ///
+    /// Used by [`establish`] once `sqlite3_open_v2` has succeeded.
+    pub(crate) fn from_raw_parts(handle: NonNull<sqlite3>) -> Self {
+        SqliteConnection {
+            handle,
+            worker: Worker::spawn(),
+            hooks: Hooks::default(),
+        }
+    }
+
+    /// Open a new connection per `options`, preloading every extension registered via
+    /// [`SqliteConnectOptions::extension`].
+    pub async fn establish(options: &SqliteConnectOptions) -> Result<Self, SqliteError> {
+        establish::establish(options).await
+    }
+
+    /// Clear every per-connection callback a caller may have registered (see
+    /// [`update_hook`](Self::update_hook) and friends), telling SQLite to stop invoking
+    /// them.
+    ///
+    /// Called by the pool's `after_release` hook (see `crate::pool`) whenever this
+    /// connection is checked back in, so the next checkout starts with a clean slate
+    /// instead of silently inheriting the previous borrower's closures.
+    pub(crate) fn reset_for_pool(&mut self) {
+        let handle = self.as_raw_handle();
+        self.hooks.clear(handle);
+    }
+}
+
+impl Drop for SqliteConnection {
+    fn drop(&mut self) {
+        // SAFETY: `self.handle` was opened by `sqlite3_open_v2` and is only ever closed
+        // here. The return code is ignored: `Drop` can't report failure, and
+        // `sqlite3_close` only fails when a statement/backup/blob/session against this
+        // handle outlived it, which borrowing `&mut SqliteConnection` for all of those
+        // already rules out.
+        unsafe {
+            sqlite3_close(self.handle.as_ptr());
+        }
+    }
+}
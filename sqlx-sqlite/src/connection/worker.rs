@@ -0,0 +1,68 @@
+use std::sync::mpsc;
+use std::thread;
+
+use tokio::sync::oneshot;
+
+/// The single background thread on which a [`SqliteConnection`](super::SqliteConnection)
+/// runs the blocking libsqlite3 calls dispatched to it, so they never block the async
+/// runtime's own threads.
+///
+/// [`SqliteConnection::run_blocking`](super::SqliteConnection::run_blocking) is the only
+/// way the rest of the driver reaches this thread. Other calls against the same handle
+/// (opening a blob, loading an extension, registering hooks, closing handles) are made
+/// inline on the calling thread instead.
+pub(crate) struct Worker {
+    jobs: mpsc::Sender<Box<dyn FnOnce() + Send + 'static>>,
+}
+
+impl Worker {
+    /// Spawn the worker thread. It runs until every [`Worker`] (and clone of its job
+    /// sender) sharing this channel is dropped, at which point `jobs.recv()` returns
+    /// `Err` and the thread exits.
+    pub(crate) fn spawn() -> Self {
+        let (jobs, rx) = mpsc::channel::<Box<dyn FnOnce() + Send + 'static>>();
+
+        thread::spawn(move || {
+            while let Ok(job) = rx.recv() {
+                job();
+            }
+        });
+
+        Worker { jobs }
+    }
+
+    /// Run `f` on the worker thread and await its result.
+    ///
+    /// `f` (and `T`) don't have to be `Send` even though the job channel requires it --
+    /// every caller in this driver closes over a raw `NonNull`/`*mut` FFI handle, which
+    /// is `!Send` by design. That's sound here because the closure runs exactly once,
+    /// synchronously, on the worker thread, and this function doesn't return until it
+    /// has (it awaits `reply_rx`): nothing `f` captured is ever touched from more than
+    /// one thread at a time, and nothing it borrowed can be dropped while it might
+    /// still run.
+    pub(crate) async fn run<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+        T: Send,
+    {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        let job: Box<dyn FnOnce() + '_> = Box::new(move || {
+            let _ = reply_tx.send(f());
+        });
+
+        // SAFETY: see the doc comment above -- `run` blocks (via `reply_rx.await`)
+        // until this exact closure has finished running on the worker thread, so
+        // erasing its `Send`-ness and lifetime here never lets it be invoked after the
+        // borrows/non-`Send` values it captured are no longer valid to touch.
+        let job: Box<dyn FnOnce() + Send + 'static> = unsafe { std::mem::transmute(job) };
+
+        self.jobs
+            .send(job)
+            .expect("SQLite connection worker thread has already exited");
+
+        reply_rx
+            .await
+            .expect("SQLite connection worker thread panicked while running a job")
+    }
+}
@@ -0,0 +1,75 @@
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::{c_char, c_int};
+
+use libsqlite3_sys::{sqlite3_errstr, sqlite3_free, SQLITE_DONE, SQLITE_OK, SQLITE_ROW};
+
+/// An error surfaced by a SQLite C API call, carrying the numeric [result code][codes]
+/// and whatever message SQLite attached to it.
+///
+/// [codes]: https://www.sqlite.org/rescode.html
+#[derive(Debug, Clone)]
+pub struct SqliteError {
+    code: c_int,
+    message: String,
+}
+
+impl SqliteError {
+    /// Turn a raw SQLite result code into `Ok(())` if it's a success code (`SQLITE_OK`,
+    /// or the row-at-a-time codes `SQLITE_ROW`/`SQLITE_DONE`), or an error built from
+    /// [`sqlite3_errstr`] otherwise.
+    pub(crate) fn from_code(code: c_int) -> Result<(), Self> {
+        if matches!(code, SQLITE_OK | SQLITE_ROW | SQLITE_DONE) {
+            return Ok(());
+        }
+
+        // SAFETY: `sqlite3_errstr` returns a static, nul-terminated string for any
+        // result code, valid or not.
+        let message = unsafe { CStr::from_ptr(sqlite3_errstr(code)) }
+            .to_string_lossy()
+            .into_owned();
+
+        Err(SqliteError { code, message })
+    }
+
+    /// Like [`from_code`][Self::from_code], but takes a SQLite-owned error message (as
+    /// written to an `errmsg` out-parameter, e.g. by `sqlite3_load_extension`) instead
+    /// of the generic `sqlite3_errstr` text, freeing it afterward.
+    pub(crate) fn from_code_with_message(code: c_int, errmsg: *mut c_char) -> Result<(), Self> {
+        if matches!(code, SQLITE_OK | SQLITE_ROW | SQLITE_DONE) {
+            return Ok(());
+        }
+
+        if errmsg.is_null() {
+            return Self::from_code(code);
+        }
+
+        // SAFETY: `errmsg` is a valid, nul-terminated string allocated by SQLite, which
+        // we own and free below per the `errmsg` out-parameter contract.
+        let message = unsafe { CStr::from_ptr(errmsg) }.to_string_lossy().into_owned();
+        // SAFETY: `errmsg` was allocated by SQLite and is only ever freed here.
+        unsafe { sqlite3_free(errmsg.cast()) };
+
+        Err(SqliteError { code, message })
+    }
+
+    /// The raw SQLite [result code][codes] this error was built from.
+    ///
+    /// [codes]: https://www.sqlite.org/rescode.html
+    pub fn code(&self) -> c_int {
+        self.code
+    }
+
+    /// The message SQLite attached to this result code.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for SqliteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (SQLite error code {})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for SqliteError {}
@@ -0,0 +1,188 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+
+use libsqlite3_sys::{
+    sqlite3, sqlite3_commit_hook, sqlite3_rollback_hook, sqlite3_update_hook, SQLITE_DELETE,
+    SQLITE_INSERT, SQLITE_UPDATE,
+};
+
+use crate::SqliteConnection;
+
+/// The kind of row change reported to an [`update_hook`][SqliteConnection::update_hook]
+/// callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl Action {
+    fn from_raw(op: c_int) -> Option<Self> {
+        match op {
+            SQLITE_INSERT => Some(Action::Insert),
+            SQLITE_UPDATE => Some(Action::Update),
+            SQLITE_DELETE => Some(Action::Delete),
+            _ => None,
+        }
+    }
+}
+
+type UpdateCallback = Box<dyn FnMut(Action, &str, &str, i64) + Send>;
+type CommitCallback = Box<dyn FnMut() -> bool + Send>;
+type RollbackCallback = Box<dyn FnMut() + Send>;
+
+/// The user-supplied callback closures backing a connection's hooks, plus whatever
+/// SQLite needs us to keep alive for as long as the hook is registered.
+///
+/// Held on [`SqliteConnection`] so hooks survive across statements on the same
+/// connection, but reset to `None` whenever the connection is returned to a pool --
+/// otherwise the next checkout would silently inherit another caller's callbacks.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    update: Option<Box<UpdateCallback>>,
+    commit: Option<Box<CommitCallback>>,
+    rollback: Option<Box<RollbackCallback>>,
+}
+
+impl Hooks {
+    /// Clear every registered hook, telling SQLite to stop invoking them.
+    ///
+    /// Called via [`SqliteConnection::reset_for_pool`] whenever a pooled connection is
+    /// checked back in (see `crate::pool`'s `after_release` hook), so the next checkout
+    /// starts with a clean slate instead of inheriting a previous caller's closures.
+    ///
+    /// Takes the raw handle rather than `&mut SqliteConnection` so this can be called
+    /// from a method that already holds `&mut self.hooks` (borrowing the whole
+    /// connection again there would conflict).
+    pub(crate) fn clear(&mut self, handle: *mut sqlite3) {
+        if self.update.is_some() {
+            // SAFETY: `handle` is a valid, open connection; passing `None` tells
+            // SQLite to stop calling back into `update_hook_trampoline`, after which
+            // it's safe to drop the boxed closure.
+            unsafe {
+                sqlite3_update_hook(handle, None, std::ptr::null_mut());
+            }
+            self.update = None;
+        }
+
+        if self.commit.is_some() {
+            // SAFETY: as above, for the commit hook.
+            unsafe {
+                sqlite3_commit_hook(handle, None, std::ptr::null_mut());
+            }
+            self.commit = None;
+        }
+
+        if self.rollback.is_some() {
+            // SAFETY: as above, for the rollback hook.
+            unsafe {
+                sqlite3_rollback_hook(handle, None, std::ptr::null_mut());
+            }
+            self.rollback = None;
+        }
+    }
+}
+
+unsafe extern "C" fn update_hook_trampoline(
+    ctx: *mut c_void,
+    op: c_int,
+    db: *const c_char,
+    table: *const c_char,
+    rowid: i64,
+) {
+    let Some(action) = Action::from_raw(op) else {
+        return;
+    };
+
+    // SAFETY: `db`/`table` are valid, nul-terminated, UTF-8 strings for the duration
+    // of this call, per the `sqlite3_update_hook` contract.
+    let db = unsafe { CStr::from_ptr(db) }.to_string_lossy();
+    let table = unsafe { CStr::from_ptr(table) }.to_string_lossy();
+
+    // SAFETY: `ctx` was set to this exact boxed closure by `update_hook` below, and
+    // outlives every call to this trampoline (it's only freed once SQLite no longer
+    // points at it: in `Hooks::clear`, or when `update_hook` replaces it).
+    let callback = unsafe { &mut *(ctx as *mut UpdateCallback) };
+    callback(action, &db, &table, rowid);
+}
+
+unsafe extern "C" fn commit_hook_trampoline(ctx: *mut c_void) -> c_int {
+    // SAFETY: see `update_hook_trampoline`.
+    let callback = unsafe { &mut *(ctx as *mut CommitCallback) };
+    // A non-zero return vetoes the commit, converting it into a rollback.
+    c_int::from(!callback())
+}
+
+unsafe extern "C" fn rollback_hook_trampoline(ctx: *mut c_void) {
+    // SAFETY: see `update_hook_trampoline`.
+    let callback = unsafe { &mut *(ctx as *mut RollbackCallback) };
+    callback();
+}
+
+impl SqliteConnection {
+    /// Register a callback to run whenever a row is inserted, updated, or deleted on
+    /// this connection (outside of changes made by `TRUNCATE` or by the session
+    /// extension's conflict resolution).
+    ///
+    /// Replaces any previously registered update hook. Useful for invalidating caches
+    /// or emitting change notifications the moment a row changes, without polling.
+    pub fn update_hook<F>(&mut self, callback: F)
+    where
+        F: FnMut(Action, &str, &str, i64) + Send + 'static,
+    {
+        let mut boxed: Box<UpdateCallback> = Box::new(Box::new(callback));
+        let ctx = boxed.as_mut() as *mut UpdateCallback as *mut c_void;
+
+        // SAFETY: `ctx` points into `boxed`, which we store on `self.hooks` below so
+        // it outlives this registration; `update_hook_trampoline` matches the
+        // signature `sqlite3_update_hook` expects.
+        unsafe {
+            sqlite3_update_hook(self.as_raw_handle(), Some(update_hook_trampoline), ctx);
+        }
+
+        self.hooks_mut().update = Some(boxed);
+    }
+
+    /// Register a callback to run just before a transaction commits.
+    ///
+    /// Return `true` to allow the commit, or `false` to veto it -- vetoing converts
+    /// the commit into a rollback. Replaces any previously registered commit hook.
+    pub fn commit_hook<F>(&mut self, callback: F)
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        let mut boxed: Box<CommitCallback> = Box::new(Box::new(callback));
+        let ctx = boxed.as_mut() as *mut CommitCallback as *mut c_void;
+
+        // SAFETY: see `update_hook`.
+        unsafe {
+            sqlite3_commit_hook(self.as_raw_handle(), Some(commit_hook_trampoline), ctx);
+        }
+
+        self.hooks_mut().commit = Some(boxed);
+    }
+
+    /// Register a callback to run whenever a transaction rolls back.
+    ///
+    /// Replaces any previously registered rollback hook.
+    pub fn rollback_hook<F>(&mut self, callback: F)
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let mut boxed: Box<RollbackCallback> = Box::new(Box::new(callback));
+        let ctx = boxed.as_mut() as *mut RollbackCallback as *mut c_void;
+
+        // SAFETY: see `update_hook`.
+        unsafe {
+            sqlite3_rollback_hook(self.as_raw_handle(), Some(rollback_hook_trampoline), ctx);
+        }
+
+        self.hooks_mut().rollback = Some(boxed);
+    }
+
+    fn hooks_mut(&mut self) -> &mut Hooks {
+        // Provided by the connection's field of the same type; see `connection/mod.rs`.
+        &mut self.hooks
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+
+use crate::{SqliteConnectOptions, SqliteConnection, SqliteError};
+
+/// One extension to preload, as configured via [`SqliteConnectOptions::extension`] or
+/// [`SqliteConnectOptions::extension_with_entrypoint`].
+#[derive(Debug, Clone)]
+pub(crate) struct PreloadExtension {
+    path: PathBuf,
+    entry_point: Option<String>,
+}
+
+impl SqliteConnectOptions {
+    /// Preload the extension shared library at `path` into every connection opened
+    /// with these options, using the library's default entry point (derived from its
+    /// file name, per SQLite's convention).
+    ///
+    /// May be called multiple times to preload several extensions. Use
+    /// [`extension_with_entrypoint`][Self::extension_with_entrypoint] if the library
+    /// needs a non-default entry point.
+    ///
+    /// This is the pool-wide counterpart of [`SqliteConnection::load_extension`]: an
+    /// application using, say, a spatial module gets it on every pooled connection
+    /// without having to call `load_extension()` by hand after every checkout.
+    pub fn extension(mut self, path: impl AsRef<Path>) -> Self {
+        self.extensions.push(PreloadExtension {
+            path: path.as_ref().to_owned(),
+            entry_point: None,
+        });
+        self
+    }
+
+    /// Like [`extension`][Self::extension], but calling `entry_point` inside the
+    /// library instead of its default entry point.
+    pub fn extension_with_entrypoint(
+        mut self,
+        path: impl AsRef<Path>,
+        entry_point: impl Into<String>,
+    ) -> Self {
+        self.extensions.push(PreloadExtension {
+            path: path.as_ref().to_owned(),
+            entry_point: Some(entry_point.into()),
+        });
+        self
+    }
+}
+
+/// Load every extension configured via [`SqliteConnectOptions::extension`] /
+/// [`extension_with_entrypoint`][SqliteConnectOptions::extension_with_entrypoint] onto
+/// a freshly-established connection.
+///
+/// Called once from the driver's connection-establishment path, before the connection
+/// is handed to the pool or the caller.
+pub(crate) async fn preload_extensions(
+    conn: &mut SqliteConnection,
+    extensions: &[PreloadExtension],
+) -> Result<(), SqliteError> {
+    for ext in extensions {
+        conn.load_extension(&ext.path, ext.entry_point.as_deref())
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+mod extension;
+
+use std::path::{Path, PathBuf};
+
+pub(crate) use extension::{preload_extensions, PreloadExtension};
+
+/// Configuration for opening a [`SqliteConnection`][crate::SqliteConnection].
+///
+/// Built up via the builder methods below (e.g. [`extension`][Self::extension]), then
+/// passed to [`establish`][crate::connection::establish::establish] by the driver's
+/// connection-establishment path.
+#[derive(Debug, Clone)]
+pub struct SqliteConnectOptions {
+    pub(crate) filename: PathBuf,
+    pub(crate) extensions: Vec<PreloadExtension>,
+}
+
+impl SqliteConnectOptions {
+    /// Start building options to open (or create) the database file at `filename`.
+    pub fn new(filename: impl AsRef<Path>) -> Self {
+        SqliteConnectOptions {
+            filename: filename.as_ref().to_owned(),
+            extensions: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use sqlx_core::pool::PoolOptions;
+
+use crate::{Sqlite, SqliteConnection};
+
+/// The [`PoolOptions`] a `SqlitePool` is built with by default.
+///
+/// Registers an [`after_release`][PoolOptions::after_release] hook that calls
+/// [`SqliteConnection::reset_for_pool`] on every connection as it's checked back in --
+/// without this, the next checkout of that same pooled connection would silently
+/// inherit whatever update/commit/rollback hooks the previous borrower registered via
+/// [`SqliteConnection::update_hook`] and friends.
+pub(crate) fn default_pool_options() -> PoolOptions<Sqlite> {
+    PoolOptions::new().after_release(|conn: &mut SqliteConnection, _meta| {
+        conn.reset_for_pool();
+        Box::pin(async { true })
+    })
+}
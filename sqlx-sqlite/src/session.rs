@@ -0,0 +1,364 @@
+use std::ffi::CStr;
+use std::os::raw::c_int;
+use std::ptr::NonNull;
+
+use libsqlite3_sys::{
+    sqlite3_changeset_iter, sqlite3changeset_apply, sqlite3changeset_invert, sqlite3changeset_op,
+    sqlite3session, sqlite3session_attach, sqlite3session_changeset, sqlite3session_create,
+    sqlite3session_delete, sqlite3session_patchset, SQLITE_CHANGESET_ABORT,
+    SQLITE_CHANGESET_CONFLICT, SQLITE_CHANGESET_CONSTRAINT, SQLITE_CHANGESET_DATA,
+    SQLITE_CHANGESET_FOREIGN_KEY, SQLITE_CHANGESET_NOTFOUND, SQLITE_CHANGESET_OMIT,
+    SQLITE_CHANGESET_REPLACE, SQLITE_OK,
+};
+
+use crate::{SqliteConnection, SqliteError};
+
+/// How to resolve a conflict encountered while applying a changeset or patchset.
+///
+/// Returned from the conflict handler passed to [`apply_changeset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictAction {
+    /// Skip this change and continue applying the rest of the changeset.
+    Omit,
+    /// Apply this change anyway, overwriting the conflicting row.
+    Replace,
+    /// Abort the entire apply operation and roll back any changes made so far.
+    Abort,
+}
+
+impl ConflictAction {
+    fn into_raw(self) -> c_int {
+        match self {
+            ConflictAction::Omit => SQLITE_CHANGESET_OMIT,
+            ConflictAction::Replace => SQLITE_CHANGESET_REPLACE,
+            ConflictAction::Abort => SQLITE_CHANGESET_ABORT,
+        }
+    }
+}
+
+/// Why [`apply_changeset`] is asking its conflict handler to make a decision.
+///
+/// Passed to the handler alongside the name of the table the conflicting change
+/// targets, via `sqlite3changeset_op`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictType {
+    /// The row being changed exists, but one or more of its values don't match what
+    /// the changeset expected them to be (i.e. it was independently modified).
+    Data,
+    /// The row being updated or deleted no longer exists in the target database.
+    NotFound,
+    /// Applying an `INSERT` would violate a `PRIMARY KEY` (or `UNIQUE`) constraint
+    /// against a row that isn't itself part of this changeset.
+    Conflict,
+    /// Applying the change would violate a `NOT NULL`, `CHECK`, or other constraint
+    /// not covered by [`Conflict`](Self::Conflict).
+    Constraint,
+    /// Applying the change would violate a foreign key constraint. Unlike the other
+    /// variants, this is reported once per violated table at the end of the apply,
+    /// not once per conflicting row.
+    ForeignKey,
+}
+
+impl ConflictType {
+    fn from_raw(raw: c_int) -> Option<Self> {
+        match raw {
+            SQLITE_CHANGESET_DATA => Some(ConflictType::Data),
+            SQLITE_CHANGESET_NOTFOUND => Some(ConflictType::NotFound),
+            SQLITE_CHANGESET_CONFLICT => Some(ConflictType::Conflict),
+            SQLITE_CHANGESET_CONSTRAINT => Some(ConflictType::Constraint),
+            SQLITE_CHANGESET_FOREIGN_KEY => Some(ConflictType::ForeignKey),
+            _ => None,
+        }
+    }
+}
+
+/// A handle to the [SQLite session extension][session], which records every row
+/// change made to one or more attached tables so it can later be serialized as a
+/// changeset or patchset and replayed elsewhere.
+///
+/// Create one with [`SqliteConnection::create_session`], [`Session::attach`] one or
+/// more tables (or pass `None` to track every table), then run statements against the
+/// connection as usual. Call [`Session::changeset`] (or [`Session::patchset`]) at any
+/// point to snapshot everything recorded so far.
+///
+/// Every method here dispatches its libsqlite3 call onto the owning connection's
+/// worker thread via [`SqliteConnection::run_blocking`], the same as
+/// [`Blob`](crate::Blob) and [`Backup`](crate::Backup) -- which is also why this holds
+/// the connection by reference rather than just the bare session handle.
+///
+/// This is the async/sqlx equivalent of rusqlite's `session` module.
+///
+/// [session]: https://www.sqlite.org/sessionintro.html
+pub struct Session<'a> {
+    conn: &'a mut SqliteConnection,
+    handle: NonNull<sqlite3session>,
+}
+
+// The session handle is only used through the `&mut SqliteConnection` held here: every
+// libsqlite3 call on it except `sqlite3session_delete` in `Drop` runs on the
+// connection's worker thread (see `SqliteConnection::run_blocking`), so it's never used
+// from two threads at once. `NonNull<T>` doesn't get this impl for free, so it's
+// spelled out here.
+unsafe impl Send for Session<'_> {}
+
+impl<'a> Session<'a> {
+    pub(crate) async fn new(conn: &'a mut SqliteConnection) -> Result<Self, SqliteError> {
+        let raw_handle = conn.as_raw_handle();
+
+        // SAFETY: `raw_handle` is a valid, open `sqlite3*` for the duration of this
+        // call, which is the only thing touching it while `run_blocking` awaits it; we
+        // check the returned code before trusting `handle`.
+        let (rc, handle) = conn
+            .run_blocking(move || {
+                let mut handle = std::ptr::null_mut();
+                let rc = unsafe {
+                    sqlite3session_create(raw_handle, c"main".as_ptr(), &mut handle)
+                };
+                (rc, handle)
+            })
+            .await;
+
+        SqliteError::from_code(rc)?;
+
+        Ok(Session {
+            conn,
+            // SAFETY: a `SQLITE_OK` return guarantees `handle` was set to a valid pointer.
+            handle: unsafe { NonNull::new_unchecked(handle) },
+        })
+    }
+
+    /// Start recording changes to `table`, or every table in the database if `table`
+    /// is `None`.
+    ///
+    /// May be called multiple times to track several tables with one `Session`.
+    pub async fn attach(&mut self, table: Option<&str>) -> Result<(), SqliteError> {
+        let table = table.map(|t| std::ffi::CString::new(t).expect("table name has no nul byte"));
+        let handle = self.handle;
+
+        // SAFETY: `handle` is valid for as long as `self` is alive, which outlives
+        // this call; `table` outlives the call too, since it's only dropped after
+        // `run_blocking` returns.
+        let rc = self
+            .conn
+            .run_blocking(move || unsafe {
+                sqlite3session_attach(
+                    handle.as_ptr(),
+                    table.as_ref().map_or(std::ptr::null(), |t| t.as_ptr()),
+                )
+            })
+            .await;
+
+        SqliteError::from_code(rc)
+    }
+
+    /// Serialize every change recorded so far into a binary changeset.
+    ///
+    /// A changeset contains the full old and new values of every changed column, which
+    /// lets [`apply_changeset`] detect conflicts precisely but makes it larger than
+    /// the equivalent [`patchset`][Self::patchset].
+    pub async fn changeset(&mut self) -> Result<Vec<u8>, SqliteError> {
+        self.serialize(sqlite3session_changeset).await
+    }
+
+    /// Serialize every change recorded so far into a binary patchset.
+    ///
+    /// A patchset omits old column values that aren't needed to apply the change,
+    /// producing a smaller buffer than [`changeset`][Self::changeset] at the cost of
+    /// weaker conflict detection when applied.
+    pub async fn patchset(&mut self) -> Result<Vec<u8>, SqliteError> {
+        self.serialize(sqlite3session_patchset).await
+    }
+
+    #[allow(clippy::type_complexity)]
+    async fn serialize(
+        &mut self,
+        f: unsafe extern "C" fn(
+            *mut sqlite3session,
+            *mut c_int,
+            *mut *mut std::os::raw::c_void,
+        ) -> c_int,
+    ) -> Result<Vec<u8>, SqliteError> {
+        let handle = self.handle;
+
+        // SAFETY: `handle` is valid for as long as `self` is alive, which outlives
+        // this call.
+        let (rc, len, data) = self
+            .conn
+            .run_blocking(move || {
+                let mut len: c_int = 0;
+                let mut data: *mut std::os::raw::c_void = std::ptr::null_mut();
+                let rc = unsafe { f(handle.as_ptr(), &mut len, &mut data) };
+                (rc, len, data)
+            })
+            .await;
+
+        SqliteError::from_code(rc)?;
+
+        let out = if data.is_null() || len == 0 {
+            Vec::new()
+        } else {
+            // SAFETY: `data` points to `len` initialized bytes per the session API
+            // contract; reading them back here (off the worker thread) is fine since
+            // it's just process memory we now own, not a libsqlite3 call.
+            unsafe { std::slice::from_raw_parts(data as *const u8, len as usize).to_vec() }
+        };
+
+        if !data.is_null() {
+            // SAFETY: `data` was allocated by SQLite and is owned by us after the call above.
+            unsafe { libsqlite3_sys::sqlite3_free(data) };
+        }
+
+        Ok(out)
+    }
+}
+
+impl Drop for Session<'_> {
+    fn drop(&mut self) {
+        // SAFETY: `self.handle` was created by `sqlite3session_create` and is only
+        // ever freed here. Not dispatched through `run_blocking` since `Drop` can't be
+        // async; freeing a session handle doesn't block on I/O the way the FFI calls
+        // above can, so running it on whatever thread drops `self` is fine.
+        unsafe { sqlite3session_delete(self.handle.as_ptr()) }
+    }
+}
+
+impl SqliteConnection {
+    /// Create a new [`Session`] recording row changes made through this connection.
+    pub async fn create_session(&mut self) -> Result<Session<'_>, SqliteError> {
+        Session::new(self).await
+    }
+}
+
+/// Apply a changeset or patchset (as produced by [`Session::changeset`] or
+/// [`Session::patchset`]) to `conn`, invoking `conflict_handler` for every row that
+/// conflicts with the target database's current state.
+///
+/// The handler receives the [`ConflictType`] and the name of the table the conflicting
+/// change targets, which is enough to decide e.g. "always prefer the incoming change
+/// for table `foo`, but never for `bar`".
+///
+/// This is how a changeset captured on one connection (e.g. an offline replica) gets
+/// replayed against another, for sync or audit-log replay workflows.
+///
+/// `sqlite3changeset_apply` is a blocking C call, so it's dispatched onto `conn`'s
+/// worker thread via [`SqliteConnection::run_blocking`], same as every other
+/// connection-bound call in this module.
+pub async fn apply_changeset(
+    conn: &mut SqliteConnection,
+    changeset: &[u8],
+    mut conflict_handler: impl FnMut(ConflictType, &str) -> ConflictAction,
+) -> Result<(), SqliteError> {
+    unsafe extern "C" fn conflict_trampoline(
+        ctx: *mut std::os::raw::c_void,
+        conflict_type: c_int,
+        iter: *mut sqlite3_changeset_iter,
+    ) -> c_int {
+        let Some(conflict_type) = ConflictType::from_raw(conflict_type) else {
+            // An unrecognized conflict type (e.g. a newer SQLite added one we don't
+            // know about yet) isn't safe to hand to the caller's handler; abort rather
+            // than guess.
+            return SQLITE_CHANGESET_ABORT;
+        };
+
+        let mut table: *const std::os::raw::c_char = std::ptr::null();
+        let mut num_cols: c_int = 0;
+        let mut op: c_int = 0;
+        let mut indirect: c_int = 0;
+
+        // SAFETY: `iter` is a valid changeset iterator for the duration of this
+        // callback, per the `sqlite3changeset_apply` contract.
+        let rc =
+            unsafe { sqlite3changeset_op(iter, &mut table, &mut num_cols, &mut op, &mut indirect) };
+
+        let table = if rc == SQLITE_OK && !table.is_null() {
+            // SAFETY: `table` is a valid, nul-terminated, UTF-8 string for the
+            // duration of this call, per the `sqlite3changeset_op` contract.
+            unsafe { CStr::from_ptr(table) }.to_string_lossy()
+        } else {
+            std::borrow::Cow::Borrowed("")
+        };
+
+        // SAFETY: `ctx` was set to this exact `&mut dyn FnMut` below, for the duration
+        // of the single `sqlite3changeset_apply` call it's used in.
+        let handler =
+            unsafe { &mut *(ctx as *mut &mut dyn FnMut(ConflictType, &str) -> ConflictAction) };
+        handler(conflict_type, &table).into_raw()
+    }
+
+    let mut handler: &mut dyn FnMut(ConflictType, &str) -> ConflictAction = &mut conflict_handler;
+    let handler_ptr = &mut handler as *mut _ as *mut std::os::raw::c_void;
+
+    let raw_handle = conn.as_raw_handle();
+    let len = changeset.len() as c_int;
+    let data = changeset.as_ptr() as *mut std::os::raw::c_void;
+
+    // SAFETY: `raw_handle` is a valid open connection; `data`/`len` describe the
+    // caller's `changeset` slice, and `handler_ptr` points at `handler` above -- both
+    // outlive this call since `run_blocking` only runs this closure once, synchronously,
+    // before this function returns. `conflict_trampoline` and `handler_ptr` satisfy the
+    // callback contract of `sqlite3changeset_apply`, and no filter callback is needed
+    // so we pass `None`.
+    let rc = conn
+        .run_blocking(move || unsafe {
+            sqlite3changeset_apply(
+                raw_handle,
+                len,
+                data,
+                None,
+                Some(conflict_trampoline),
+                handler_ptr,
+            )
+        })
+        .await;
+
+    SqliteError::from_code(rc)
+}
+
+/// Produce the inverse of `changeset`: applying the result undoes everything the
+/// original changeset did (and vice versa).
+///
+/// Useful for implementing undo, or for rolling back a changeset that was already
+/// applied and then found to be unwanted.
+///
+/// Unlike every other function in this module, this doesn't take a `&mut SqliteConnection`
+/// -- `sqlite3changeset_invert` is a pure transformation over the changeset bytes with
+/// no connection involved, so there's no connection worker thread to dispatch it onto.
+/// It's still a blocking call for a large changeset, so it runs on the async runtime's
+/// blocking thread pool via `tokio::task::spawn_blocking` instead of inline.
+pub async fn invert_changeset(changeset: &[u8]) -> Result<Vec<u8>, SqliteError> {
+    let changeset = changeset.to_vec();
+
+    tokio::task::spawn_blocking(move || {
+        let mut out_len: c_int = 0;
+        let mut out_data: *mut std::os::raw::c_void = std::ptr::null_mut();
+
+        // SAFETY: `changeset` outlives the call; `out_data`/`out_len` are only trusted
+        // after checking the returned code.
+        let rc = unsafe {
+            sqlite3changeset_invert(
+                changeset.len() as c_int,
+                changeset.as_ptr() as *const std::os::raw::c_void,
+                &mut out_len,
+                &mut out_data,
+            )
+        };
+
+        SqliteError::from_code(rc)?;
+
+        let out = if out_data.is_null() || out_len == 0 {
+            Vec::new()
+        } else {
+            // SAFETY: `out_data` points to `out_len` initialized bytes per the API contract.
+            unsafe { std::slice::from_raw_parts(out_data as *const u8, out_len as usize).to_vec() }
+        };
+
+        if !out_data.is_null() {
+            // SAFETY: `out_data` was allocated by SQLite and is owned by us after the call above.
+            unsafe { libsqlite3_sys::sqlite3_free(out_data) };
+        }
+
+        Ok(out)
+    })
+    .await
+    .expect("blocking task panicked")
+}